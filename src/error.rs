@@ -0,0 +1,115 @@
+//! Error codes returned by the contract entrypoints.
+//!
+//! Codes are part of the public API: clients (e.g. `frontend/server`) map
+//! them to UI messages, so existing values must never be renumbered. Codes
+//! are grouped by area, leaving room to add new ones within each group.
+//! The contract spec admits at most 50 codes, so related failures share one
+//! where clients do not need to tell them apart.
+
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    // Administration and roles (1-9)
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    /// The caller lacks the required role, or its account is not active.
+    Unauthorized = 3,
+    NoPendingAdmin = 4,
+    RoleNotGranted = 5,
+    /// The admin role is assigned through `transfer_admin`.
+    AdminRoleNotGrantable = 6,
+    AlreadyMigrated = 7,
+    /// A threshold exceeds its `extend_to`, or `extend_to` exceeds the
+    /// network's maximum TTL.
+    InvalidTtlConfig = 8,
+
+    // Users (10-19)
+    /// Also while the comments of a deleted profile are being purged.
+    UserAlreadyRegistered = 10,
+    UserNotFound = 11,
+
+    // Destinations (20-29)
+    /// Also while the comments of a deleted destination are being purged.
+    DestinationAlreadyExists = 20,
+    DestinationNotFound = 21,
+    UnitAlreadyExists = 22,
+    UnitNotFound = 23,
+    TooManyUnits = 24,
+    /// Stay limits must satisfy `1 <= min_stay <= max_stay <= 90`, and a
+    /// unit must take at least one guest.
+    InvalidUnit = 25,
+    /// Seasons are empty, unsorted or overlapping, a percentage exceeds
+    /// 100%, or there are more than 20 seasons or stay discounts.
+    InvalidPricingRules = 26,
+
+    // Comments (30-39)
+    CommentNotFound = 30,
+    /// Ratings go from 1 to 5.
+    InvalidRating = 31,
+    /// Reviews need a completed stay of the reviewer at the destination.
+    StayNotCompleted = 32,
+    /// The reservation was already reviewed.
+    AlreadyReviewed = 33,
+
+    // Reservations (40-59)
+    ReservationAlreadyExists = 40,
+    ReservationNotFound = 41,
+    /// A legacy ISO date could not be parsed during migration.
+    InvalidDate = 42,
+    /// `check_out` is not after `check_in`.
+    InvalidDateRange = 43,
+    PriceMismatch = 44,
+    PriceOverflow = 45,
+    ReservationCancelled = 46,
+    /// `check_in` is not after the current ledger day.
+    CheckInNotInFuture = 47,
+    StayTooLong = 48,
+    /// The unit is fully booked for at least one night of the stay.
+    NotAvailable = 49,
+    StayTooShort = 50,
+    /// A reservation needs at least one guest, and at most the unit's
+    /// `max_guests`.
+    InvalidGuests = 51,
+    /// The status of the reservation, user or destination does not allow
+    /// this action, or a user, destination or unit with pending, paid or
+    /// checked-in reservations was to be deleted.
+    InvalidStatus = 52,
+    /// Guests check in from the check-in day until the day before check-out.
+    OutsideCheckInWindow = 53,
+    /// A no-show is recorded once the check-in day is over.
+    CheckInDayNotOver = 54,
+    /// Holds last between 1 minute and 7 days.
+    InvalidHoldDuration = 55,
+    /// The reservation was not paid before its hold expired.
+    HoldExpired = 56,
+    /// The destination, or the guest, has too many unpaid reservations on
+    /// hold.
+    TooManyHolds = 57,
+
+    // Payments (60-69)
+    PayoutNotConfigured = 60,
+    AlreadyPaid = 61,
+    /// No payment of the reservation is held in escrow: it was never paid,
+    /// or already released or refunded.
+    PaymentNotHeld = 62,
+    /// Escrowed payments are released from the check-out day on.
+    StayNotFinished = 63,
+
+    // Booking tokens (70-79)
+    TokenNotFound = 70,
+    NotTokenOwner = 71,
+    NotApproved = 72,
+
+    // Media (80-89)
+    MediaNotFound = 80,
+    /// A destination's gallery holds up to 20 items.
+    TooManyMedia = 81,
+    /// The new order must list every gallery index exactly once.
+    InvalidMediaOrder = 82,
+    /// The SHA-256 digest is all zeros, the IPFS content id is neither
+    /// CIDv0 nor base32 CIDv1, or the size is not between 1 byte and 50 MiB.
+    InvalidMediaContent = 83,
+}
@@ -0,0 +1,310 @@
+//! Contract events consumed by the off-chain indexer.
+//!
+//! Every state change publishes one event. The first topic names the record
+//! kind, the second the action and the third the id of the affected record,
+//! so indexers can subscribe by kind or follow a single record:
+//!
+//! | Topics                                   | Data                             |
+//! |------------------------------------------|----------------------------------|
+//! | `("admin", "init", admin)`               | `()`                             |
+//! | `("admin", "proposed", new_admin)`       | `()`                             |
+//! | `("admin", "accepted", new_admin)`       | `()`                             |
+//! | `("admin", "ttl", admin)`                | `TtlConfig`                      |
+//! | `("admin", "hold", admin)`               | `minutes: u32`                   |
+//! | `("admin", "delmode", admin)`            | `DeleteMode`                     |
+//! | `("admin", "upgrade", admin)`            | `wasm_hash: BytesN<32>`          |
+//! | `("role", "granted", account)`           | `Role`                           |
+//! | `("role", "revoked", account)`           | `Role`                           |
+//! | `("user", "registered", user_id)`        | `()`                             |
+//! | `("user", "updated", user_id)`           | `()`                             |
+//! | `("user", "deleted", user_id)`           | `()`                             |
+//! | `("user", "status", user_id)`            | `(RecordStatus, caller: Address)`|
+//! | `("dest", "created", dest_id)`           | `caller: Address`                |
+//! | `("dest", "updated", dest_id)`           | `caller: Address`                |
+//! | `("dest", "deleted", dest_id)`           | `caller: Address`                |
+//! | `("dest", "status", dest_id)`            | `(RecordStatus, caller: Address)`|
+//! | `("dest", "payout", dest_id)`            | `PayoutConfig`                   |
+//! | `("dest", "pricing", dest_id)`           | `PricingRules`                   |
+//! | `("dest", "policy", dest_id)`            | `CancellationPolicy`             |
+//! | `("unit", "created", dest_id)`           | `(unit_id: String, caller)`      |
+//! | `("unit", "updated", dest_id)`           | `(unit_id: String, caller)`      |
+//! | `("unit", "deleted", dest_id)`           | `(unit_id: String, caller)`      |
+//! | `("media", "added", dest_id)`            | `Media`                          |
+//! | `("media", "removed", dest_id)`          | `(Media, caller: Address)`       |
+//! | `("media", "reordered", dest_id)`        | `caller: Address`                |
+//! | `("media", "cover", dest_id)`            | `(Media, caller: Address)`       |
+//! | `("media", "dropped", dest_id)`          | `(LegacyMedia, caller: Address)` |
+//! | `("comment", "added", dest_id)`          | `(id: u64, user_id, rating: u32)`|
+//! | `("comment", "edited", dest_id)`         | `(id: u64, rating: u32)`         |
+//! | `("comment", "removed", dest_id)`        | `(id: u64, caller: Address)`     |
+//! | `("res", "created", reservation_id)`     | `(user_id, dest_id, total_price)`|
+//! | `("res", "cancelled", reservation_id)`   | `user_id: Address`               |
+//! | `("res", "expired", reservation_id)`     | `user_id: Address`               |
+//! | `("res", "paid", reservation_id)`        | `Payment`                        |
+//! | `("res", "checkedin", reservation_id)`   | `caller: Address`                |
+//! | `("res", "noshow", reservation_id)`      | `caller: Address`                |
+//! | `("res", "completed", reservation_id)`   | `caller: Address`                |
+//! | `("res", "released", reservation_id)`    | `(payout: Address, amount: i128)`|
+//! | `("res", "refunded", reservation_id)`    | `(guest: Address, amount: i128)` |
+//! | `("nft", "minted", token_id)`            | `owner: Address`                 |
+//! | `("nft", "transfer", token_id)`          | `(from: Address, to: Address)`   |
+//! | `("nft", "approved", token_id)`          | `(owner, approved: Address)`     |
+//! | `("nft", "burned", token_id)`            | `owner: Address`                 |
+//!
+//! User events carry no profile data so contact details never reach the
+//! event stream.
+
+use soroban_sdk::{symbol_short, Address, BytesN, Env, String, Symbol};
+
+use crate::types::{
+    CancellationPolicy, DeleteMode, LegacyMedia, Media, Payment, PayoutConfig, PricingRules,
+    RecordStatus, Role, TtlConfig,
+};
+
+pub(crate) fn admin_initialized(env: &Env, admin: &Address) {
+    let topics = (symbol_short!("admin"), symbol_short!("init"), admin.clone());
+    env.events().publish(topics, ());
+}
+
+pub(crate) fn admin_proposed(env: &Env, new_admin: &Address) {
+    let topics = (symbol_short!("admin"), symbol_short!("proposed"), new_admin.clone());
+    env.events().publish(topics, ());
+}
+
+pub(crate) fn admin_accepted(env: &Env, new_admin: &Address) {
+    let topics = (symbol_short!("admin"), symbol_short!("accepted"), new_admin.clone());
+    env.events().publish(topics, ());
+}
+
+pub(crate) fn ttl_config_set(env: &Env, admin: &Address, config: &TtlConfig) {
+    let topics = (symbol_short!("admin"), symbol_short!("ttl"), admin.clone());
+    env.events().publish(topics, config.clone());
+}
+
+pub(crate) fn hold_minutes_set(env: &Env, admin: &Address, minutes: u32) {
+    let topics = (symbol_short!("admin"), symbol_short!("hold"), admin.clone());
+    env.events().publish(topics, minutes);
+}
+
+pub(crate) fn delete_mode_set(env: &Env, admin: &Address, mode: DeleteMode) {
+    let topics = (symbol_short!("admin"), symbol_short!("delmode"), admin.clone());
+    env.events().publish(topics, mode);
+}
+
+pub(crate) fn contract_upgraded(env: &Env, admin: &Address, wasm_hash: &BytesN<32>) {
+    let topics = (symbol_short!("admin"), symbol_short!("upgrade"), admin.clone());
+    env.events().publish(topics, wasm_hash.clone());
+}
+
+pub(crate) fn role_granted(env: &Env, account: &Address, role: &Role) {
+    let topics = (symbol_short!("role"), symbol_short!("granted"), account.clone());
+    env.events().publish(topics, role.clone());
+}
+
+pub(crate) fn role_revoked(env: &Env, account: &Address, role: &Role) {
+    let topics = (symbol_short!("role"), symbol_short!("revoked"), account.clone());
+    env.events().publish(topics, role.clone());
+}
+
+pub(crate) fn user_registered(env: &Env, user_id: &Address) {
+    let topics = (symbol_short!("user"), Symbol::new(env, "registered"), user_id.clone());
+    env.events().publish(topics, ());
+}
+
+pub(crate) fn user_updated(env: &Env, user_id: &Address) {
+    let topics = (symbol_short!("user"), symbol_short!("updated"), user_id.clone());
+    env.events().publish(topics, ());
+}
+
+pub(crate) fn user_deleted(env: &Env, user_id: &Address) {
+    let topics = (symbol_short!("user"), symbol_short!("deleted"), user_id.clone());
+    env.events().publish(topics, ());
+}
+
+pub(crate) fn user_status_changed(
+    env: &Env,
+    user_id: &Address,
+    status: RecordStatus,
+    caller: &Address,
+) {
+    let topics = (symbol_short!("user"), symbol_short!("status"), user_id.clone());
+    env.events().publish(topics, (status, caller.clone()));
+}
+
+pub(crate) fn destination_created(env: &Env, dest_id: &String, caller: &Address) {
+    let topics = (symbol_short!("dest"), symbol_short!("created"), dest_id.clone());
+    env.events().publish(topics, caller.clone());
+}
+
+pub(crate) fn destination_updated(env: &Env, dest_id: &String, caller: &Address) {
+    let topics = (symbol_short!("dest"), symbol_short!("updated"), dest_id.clone());
+    env.events().publish(topics, caller.clone());
+}
+
+pub(crate) fn destination_deleted(env: &Env, dest_id: &String, caller: &Address) {
+    let topics = (symbol_short!("dest"), symbol_short!("deleted"), dest_id.clone());
+    env.events().publish(topics, caller.clone());
+}
+
+pub(crate) fn destination_status_changed(
+    env: &Env,
+    dest_id: &String,
+    status: RecordStatus,
+    caller: &Address,
+) {
+    let topics = (symbol_short!("dest"), symbol_short!("status"), dest_id.clone());
+    env.events().publish(topics, (status, caller.clone()));
+}
+
+pub(crate) fn payout_set(env: &Env, dest_id: &String, config: &PayoutConfig) {
+    let topics = (symbol_short!("dest"), symbol_short!("payout"), dest_id.clone());
+    env.events().publish(topics, config.clone());
+}
+
+pub(crate) fn pricing_set(env: &Env, dest_id: &String, rules: &PricingRules) {
+    let topics = (symbol_short!("dest"), symbol_short!("pricing"), dest_id.clone());
+    env.events().publish(topics, rules.clone());
+}
+
+pub(crate) fn cancellation_policy_set(env: &Env, dest_id: &String, policy: &CancellationPolicy) {
+    let topics = (symbol_short!("dest"), symbol_short!("policy"), dest_id.clone());
+    env.events().publish(topics, *policy);
+}
+
+pub(crate) fn unit_created(env: &Env, dest_id: &String, unit_id: &String, caller: &Address) {
+    let topics = (symbol_short!("unit"), symbol_short!("created"), dest_id.clone());
+    env.events().publish(topics, (unit_id.clone(), caller.clone()));
+}
+
+pub(crate) fn unit_updated(env: &Env, dest_id: &String, unit_id: &String, caller: &Address) {
+    let topics = (symbol_short!("unit"), symbol_short!("updated"), dest_id.clone());
+    env.events().publish(topics, (unit_id.clone(), caller.clone()));
+}
+
+pub(crate) fn unit_deleted(env: &Env, dest_id: &String, unit_id: &String, caller: &Address) {
+    let topics = (symbol_short!("unit"), symbol_short!("deleted"), dest_id.clone());
+    env.events().publish(topics, (unit_id.clone(), caller.clone()));
+}
+
+pub(crate) fn media_added(env: &Env, dest_id: &String, media: &Media) {
+    let topics = (symbol_short!("media"), symbol_short!("added"), dest_id.clone());
+    env.events().publish(topics, media.clone());
+}
+
+pub(crate) fn media_removed(env: &Env, dest_id: &String, media: &Media, caller: &Address) {
+    let topics = (symbol_short!("media"), symbol_short!("removed"), dest_id.clone());
+    env.events().publish(topics, (media.clone(), caller.clone()));
+}
+
+pub(crate) fn media_reordered(env: &Env, dest_id: &String, caller: &Address) {
+    let topics = (symbol_short!("media"), symbol_short!("reordered"), dest_id.clone());
+    env.events().publish(topics, caller.clone());
+}
+
+pub(crate) fn media_cover_set(env: &Env, dest_id: &String, media: &Media, caller: &Address) {
+    let topics = (symbol_short!("media"), symbol_short!("cover"), dest_id.clone());
+    env.events().publish(topics, (media.clone(), caller.clone()));
+}
+
+pub(crate) fn legacy_media_removed(
+    env: &Env,
+    dest_id: &String,
+    media: &LegacyMedia,
+    caller: &Address,
+) {
+    let topics = (symbol_short!("media"), symbol_short!("dropped"), dest_id.clone());
+    env.events().publish(topics, (media.clone(), caller.clone()));
+}
+
+pub(crate) fn comment_added(
+    env: &Env,
+    dest_id: &String,
+    comment_id: u64,
+    user_id: &Address,
+    rating: u32,
+) {
+    let topics = (symbol_short!("comment"), symbol_short!("added"), dest_id.clone());
+    env.events().publish(topics, (comment_id, user_id.clone(), rating));
+}
+
+pub(crate) fn comment_edited(env: &Env, dest_id: &String, comment_id: u64, rating: u32) {
+    let topics = (symbol_short!("comment"), symbol_short!("edited"), dest_id.clone());
+    env.events().publish(topics, (comment_id, rating));
+}
+
+pub(crate) fn comment_removed(env: &Env, dest_id: &String, comment_id: u64, caller: &Address) {
+    let topics = (symbol_short!("comment"), symbol_short!("removed"), dest_id.clone());
+    env.events().publish(topics, (comment_id, caller.clone()));
+}
+
+pub(crate) fn reservation_created(
+    env: &Env,
+    reservation_id: &String,
+    user_id: &Address,
+    dest_id: &String,
+    total_price: u64,
+) {
+    let topics = (symbol_short!("res"), symbol_short!("created"), reservation_id.clone());
+    env.events()
+        .publish(topics, (user_id.clone(), dest_id.clone(), total_price));
+}
+
+pub(crate) fn reservation_cancelled(env: &Env, reservation_id: &String, user_id: &Address) {
+    let topics = (symbol_short!("res"), symbol_short!("cancelled"), reservation_id.clone());
+    env.events().publish(topics, user_id.clone());
+}
+
+pub(crate) fn reservation_expired(env: &Env, reservation_id: &String, user_id: &Address) {
+    let topics = (symbol_short!("res"), symbol_short!("expired"), reservation_id.clone());
+    env.events().publish(topics, user_id.clone());
+}
+
+pub(crate) fn reservation_paid(env: &Env, reservation_id: &String, payment: &Payment) {
+    let topics = (symbol_short!("res"), symbol_short!("paid"), reservation_id.clone());
+    env.events().publish(topics, payment.clone());
+}
+
+pub(crate) fn reservation_checked_in(env: &Env, reservation_id: &String, caller: &Address) {
+    let topics = (symbol_short!("res"), symbol_short!("checkedin"), reservation_id.clone());
+    env.events().publish(topics, caller.clone());
+}
+
+pub(crate) fn reservation_no_show(env: &Env, reservation_id: &String, caller: &Address) {
+    let topics = (symbol_short!("res"), symbol_short!("noshow"), reservation_id.clone());
+    env.events().publish(topics, caller.clone());
+}
+
+pub(crate) fn reservation_completed(env: &Env, reservation_id: &String, caller: &Address) {
+    let topics = (symbol_short!("res"), symbol_short!("completed"), reservation_id.clone());
+    env.events().publish(topics, caller.clone());
+}
+
+pub(crate) fn payment_released(env: &Env, reservation_id: &String, payout: &Address, amount: i128) {
+    let topics = (symbol_short!("res"), symbol_short!("released"), reservation_id.clone());
+    env.events().publish(topics, (payout.clone(), amount));
+}
+
+pub(crate) fn payment_refunded(env: &Env, reservation_id: &String, guest: &Address, amount: i128) {
+    let topics = (symbol_short!("res"), symbol_short!("refunded"), reservation_id.clone());
+    env.events().publish(topics, (guest.clone(), amount));
+}
+
+pub(crate) fn token_minted(env: &Env, token_id: u64, owner: &Address) {
+    let topics = (symbol_short!("nft"), symbol_short!("minted"), token_id);
+    env.events().publish(topics, owner.clone());
+}
+
+pub(crate) fn token_transferred(env: &Env, token_id: u64, from: &Address, to: &Address) {
+    let topics = (symbol_short!("nft"), symbol_short!("transfer"), token_id);
+    env.events().publish(topics, (from.clone(), to.clone()));
+}
+
+pub(crate) fn token_approved(env: &Env, token_id: u64, owner: &Address, approved: &Address) {
+    let topics = (symbol_short!("nft"), symbol_short!("approved"), token_id);
+    env.events().publish(topics, (owner.clone(), approved.clone()));
+}
+
+pub(crate) fn token_burned(env: &Env, token_id: u64, owner: &Address) {
+    let topics = (symbol_short!("nft"), symbol_short!("burned"), token_id);
+    env.events().publish(topics, owner.clone());
+}
@@ -0,0 +1,94 @@
+//! Persistent storage access with TTL management.
+//!
+//! Every read or write of a persistent entry through these helpers extends
+//! its TTL once it drops below the configured threshold, so records that are
+//! in use never get archived. Entries nobody touches are kept alive by the
+//! `extend_all` maintenance entrypoint.
+
+use soroban_sdk::{Env, IntoVal, Symbol, TryFromVal, Val};
+
+use crate::types::TtlConfig;
+
+/// Average ledger close time, in seconds.
+const LEDGER_SECONDS: u64 = 5;
+
+/// Ledgers closed per day at ~5 seconds per ledger.
+const DAY_IN_LEDGERS: u32 = 17_280;
+
+/// Thresholds used until the admin calls `set_ttl_config`.
+pub(crate) const DEFAULT_TTL_CONFIG: TtlConfig = TtlConfig {
+    threshold: 30 * DAY_IN_LEDGERS,
+    extend_to: 90 * DAY_IN_LEDGERS,
+    instance_threshold: 7 * DAY_IN_LEDGERS,
+    instance_extend_to: 30 * DAY_IN_LEDGERS,
+};
+
+pub(crate) fn ttl_config(env: &Env) -> TtlConfig {
+    env.storage()
+        .instance()
+        .get(&Symbol::new(env, "ttl_config"))
+        .unwrap_or(DEFAULT_TTL_CONFIG)
+}
+
+/// Extends the contract instance (and its code), called on every entrypoint.
+pub(crate) fn bump_instance(env: &Env) {
+    let config = ttl_config(env);
+    env.storage()
+        .instance()
+        .extend_ttl(config.instance_threshold, config.instance_extend_to);
+}
+
+pub(crate) fn get<K, V>(env: &Env, key: &K) -> Option<V>
+where
+    K: IntoVal<Env, Val>,
+    V: TryFromVal<Env, Val>,
+{
+    let value = env.storage().persistent().get(key);
+    if value.is_some() {
+        extend(env, key);
+    }
+    value
+}
+
+pub(crate) fn set<K, V>(env: &Env, key: &K, value: &V)
+where
+    K: IntoVal<Env, Val>,
+    V: IntoVal<Env, Val>,
+{
+    env.storage().persistent().set(key, value);
+    extend(env, key);
+}
+
+/// Whether `key` exists; extends it if it does.
+pub(crate) fn has<K: IntoVal<Env, Val>>(env: &Env, key: &K) -> bool {
+    let found = env.storage().persistent().has(key);
+    if found {
+        extend(env, key);
+    }
+    found
+}
+
+/// Extends `key` if it exists, without reading it.
+pub(crate) fn touch<K: IntoVal<Env, Val>>(env: &Env, key: &K) {
+    has(env, key);
+}
+
+/// Writes a temporary entry that lives for at least `seconds`; it is
+/// dropped by the network once that TTL runs out.
+pub(crate) fn set_temporary<K, V>(env: &Env, key: &K, value: &V, seconds: u64)
+where
+    K: IntoVal<Env, Val>,
+    V: IntoVal<Env, Val>,
+{
+    let ledgers = (seconds / LEDGER_SECONDS + 1).min(env.storage().max_ttl() as u64) as u32;
+    let storage = env.storage().temporary();
+    storage.set(key, value);
+    storage.extend_ttl(key, ledgers, ledgers);
+}
+
+fn extend<K: IntoVal<Env, Val>>(env: &Env, key: &K) {
+    let config = ttl_config(env);
+    env.storage()
+        .persistent()
+        .extend_ttl(key, config.threshold, config.extend_to);
+}
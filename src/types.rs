@@ -0,0 +1,363 @@
+//! Typed records stored by the contract and returned from its public API.
+
+use soroban_sdk::{contracttype, Address, BytesN, String, Vec};
+
+/// Profile data of a registered user (RF-01..RF-05). Contact details never
+/// reach the ledger: each `*_hash` is the SHA-256 of a random salt followed
+/// by the value, computed off-chain, that `verify_contact` checks preimages
+/// against. An all-zero hash stands for a detail not committed to.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct User {
+    /// Display name, which may be a pseudonym.
+    pub name: String,
+    pub email_hash: BytesN<32>,
+    pub phone_hash: BytesN<32>,
+    pub birth_date_hash: BytesN<32>,
+    pub gender_hash: BytesN<32>,
+}
+
+/// Contact details committed to in a `User` profile.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ContactField {
+    Email,
+    Phone,
+    BirthDate,
+    Gender,
+}
+
+/// Destination metadata (RF-06..RF-09).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Destination {
+    pub name: String,
+    pub address: String,
+    pub location: String,
+    pub description: String,
+    /// Average rating of the reviews in hundredths of a star (450 = 4.5),
+    /// see `RatingSummary` (RF-11).
+    pub rating: u32,
+}
+
+/// Bookable unit of a destination: a room type, a tour, a block of seats...
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Unit {
+    pub name: String,
+    /// Reservations the unit can hold on the same night.
+    pub capacity: u32,
+    /// Base price per night, in `token` units.
+    pub nightly_price: u64,
+    /// SEP-41 token the unit is priced and paid in.
+    pub token: Address,
+    /// Shortest and longest accepted stay, in nights.
+    pub min_stay: u32,
+    pub max_stay: u32,
+    /// Most guests a single reservation may bring.
+    pub max_guests: u32,
+}
+
+/// Reservation record (RF-12..RF-15). Dates are unix day numbers (days
+/// since 1970-01-01 UTC); the guest stays the nights `check_in..check_out`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Reservation {
+    pub user_id: Address,
+    pub dest_id: String,
+    pub unit_id: String,
+    pub check_in: u64,
+    pub check_out: u64,
+    pub guests: u32,
+    pub total_price: u64,
+    /// SEP-41 token the stay was quoted in, and is paid and refunded in.
+    /// `None` for reservations migrated from the legacy contract, which
+    /// never took payments.
+    pub token: Option<Address>,
+    pub status: ReservationStatus,
+    /// Every status the reservation went through, oldest first.
+    pub history: Vec<StatusChange>,
+}
+
+/// Lifecycle of a reservation:
+///
+/// ```text
+/// Pending ─pay─> Paid ─check_in_guest─> CheckedIn ─release_payment─> Completed
+///    │ │          ├─mark_no_show─> NoShow    │
+///    │ └──────────┴──────────┬───────────────┘
+///    │                       └─cancel / refund─> Cancelled | Refunded
+///    └─hold expires─> Expired
+/// ```
+///
+/// Guests cancel while `Pending` or `Paid`; hosts may refund up to
+/// `CheckedIn`. A cancellation ends in `Refunded` when the whole payment
+/// went back to the guest and in `Cancelled` otherwise. A `Pending`
+/// reservation not paid before its hold expires ends `Expired`.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReservationStatus {
+    Pending,
+    Paid,
+    CheckedIn,
+    Completed,
+    Cancelled,
+    NoShow,
+    Refunded,
+    Expired,
+}
+
+/// Status a reservation entered and the ledger timestamp it did so at.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StatusChange {
+    pub status: ReservationStatus,
+    pub timestamp: u64,
+}
+
+/// Pricing rules of a destination, applied by `quote` on top of the base
+/// nightly price of the reserved unit. Percentages are in basis points
+/// (10_000 = 100%).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PricingRules {
+    /// Date ranges with their own nightly rate, sorted and not overlapping.
+    pub seasons: Vec<Season>,
+    /// Added to the nightly rate of Friday and Saturday nights.
+    pub weekend_surcharge_bps: u32,
+    /// Discounts on the whole stay; the largest one the stay qualifies for
+    /// applies.
+    pub stay_discounts: Vec<StayDiscount>,
+    /// Guests covered by the nightly rate.
+    pub included_guests: u32,
+    /// Charged per night for each guest beyond `included_guests`.
+    pub extra_guest_fee: u64,
+}
+
+/// Nights `start..end` (unix day numbers) priced at `rate`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Season {
+    pub start: u64,
+    pub end: u64,
+    pub rate: SeasonRate,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SeasonRate {
+    /// The unit's base price scaled by the given basis points.
+    Multiplier(u32),
+    /// Nightly price replacing the unit's base price.
+    Fixed(u64),
+}
+
+/// Discount on stays of at least `min_nights` nights.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StayDiscount {
+    pub min_nights: u32,
+    pub discount_bps: u32,
+}
+
+/// Share of an escrowed payment refunded when the guest cancels, by whole
+/// days left before check-in. The host keeps the rest as cancellation fee.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CancellationPolicy {
+    /// Full refund until the day before check-in.
+    Flexible,
+    /// Full refund until 5 days before check-in, half until the day before.
+    Moderate,
+    /// Full refund until 14 days before check-in, half until 7 days before.
+    Strict,
+}
+
+impl CancellationPolicy {
+    /// Refunded share, in basis points, of a cancellation `days_before`
+    /// days before check-in.
+    pub fn refund_bps(&self, days_before: u64) -> u32 {
+        let (full, half) = match self {
+            CancellationPolicy::Flexible => (1, 1),
+            CancellationPolicy::Moderate => (5, 1),
+            CancellationPolicy::Strict => (14, 7),
+        };
+        if days_before >= full {
+            10_000
+        } else if days_before >= half {
+            5_000
+        } else {
+            0
+        }
+    }
+}
+
+/// Comment and rating left on a destination (RF-11).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Comment {
+    pub id: u64,
+    pub dest_id: String,
+    pub user_id: Address,
+    /// Reviewed stay; `None` for comments written before reviews required
+    /// one.
+    pub reservation_id: Option<String>,
+    pub comment: String,
+    pub rating: u32,
+    /// Ledger timestamps of the first and of the latest version.
+    pub created_at: u64,
+    pub updated_at: u64,
+}
+
+/// Aggregate of the ratings of a destination's reviews, kept exact so the
+/// average does not drift as reviews come and go.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RatingSummary {
+    pub count: u32,
+    pub sum: u64,
+    /// Number of reviews per rating: index 0 counts 1-star reviews, index 4
+    /// 5-star reviews.
+    pub histogram: Vec<u32>,
+}
+
+impl RatingSummary {
+    /// Average rating in hundredths of a star, rounded to the nearest; 0
+    /// without reviews.
+    pub fn average(&self) -> u32 {
+        if self.count == 0 {
+            return 0;
+        }
+        let count = self.count as u64;
+        ((self.sum * 100 + count / 2) / count) as u32
+    }
+}
+
+/// Multimedia item attached to a destination (RF-10). Clients check the
+/// content fetched from `url`, or from IPFS by `cid`, against `sha256` and
+/// `size` to detect swapped files.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Media {
+    pub url: String,
+    pub media_type: MediaType,
+    /// SHA-256 digest of the file, computed off-chain.
+    pub sha256: BytesN<32>,
+    /// IPFS content id of the file, CIDv0 (`Qm...`) or base32 CIDv1 (`b...`).
+    pub cid: Option<String>,
+    /// File size in bytes.
+    pub size: u64,
+    pub uploader: Address,
+}
+
+/// Gallery item of the legacy contract, without content hash. Kept apart
+/// by `migrate_storage` until managers upload it again with `upload_media`
+/// or drop it with `remove_legacy_media`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyMedia {
+    pub url: String,
+    /// Free-form type, e.g. a MIME type.
+    pub media_type: String,
+}
+
+/// Accepted media formats.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MediaType {
+    /// `image/jpeg`
+    Jpeg,
+    /// `image/png`
+    Png,
+    /// `image/webp`
+    Webp,
+    /// `video/mp4`
+    Mp4,
+    /// `video/webm`
+    Webm,
+}
+
+/// Where reservation payments for a destination are settled. The token
+/// paid in is the currency of the reserved unit.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayoutConfig {
+    /// Address receiving the funds.
+    pub payout: Address,
+}
+
+/// Settled payment of a reservation.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Payment {
+    pub token: Address,
+    pub amount: i128,
+    pub tx_ref: String,
+}
+
+/// Booking token minted to the guest when a reservation is paid. Whoever
+/// holds the token is the guest of the reservation.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BookingToken {
+    pub owner: Address,
+    pub reservation_id: String,
+    pub dest_id: String,
+    pub unit_id: String,
+    pub check_in: u64,
+    pub check_out: u64,
+}
+
+/// TTL thresholds, in ledgers. Entries whose TTL drops below a threshold
+/// are extended to the matching `extend_to` when accessed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TtlConfig {
+    /// Persistent records (users, destinations, reservations, ...).
+    pub threshold: u32,
+    pub extend_to: u32,
+    /// Contract instance: admin, roles configuration and counters.
+    pub instance_threshold: u32,
+    pub instance_extend_to: u32,
+}
+
+/// Lifecycle status of a user or destination; `Active` unless changed.
+/// Only active users authenticate, book and review, and only active
+/// destinations take bookings.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RecordStatus {
+    Active,
+    /// Deactivated by its owner, or deleted in `DeleteMode::Deactivate`;
+    /// the owner may reactivate it.
+    Inactive,
+    /// Suspended by staff, who alone may reactivate it.
+    Suspended,
+}
+
+/// What `delete_user` and `delete_destination` do. Either way, a user or
+/// destination with pending, paid or checked-in reservations cannot be
+/// deleted.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DeleteMode {
+    /// Remove the record and its data; its comments are removed in batches
+    /// by `purge_deleted`.
+    Purge,
+    /// Keep the record as `Inactive`, with its comments. User profiles are
+    /// left blank.
+    Deactivate,
+}
+
+/// Roles granted to accounts besides the single contract administrator.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Role {
+    /// The contract administrator, assigned through `initialize` and
+    /// `transfer_admin` rather than `grant_role`.
+    Admin,
+    /// Agency staff: manage every destination, its media and moderation.
+    Operator,
+    /// Partner host of the given destination id: manages that destination's
+    /// details and media.
+    Host(String),
+}